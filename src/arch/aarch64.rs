@@ -0,0 +1,167 @@
+//! aarch64 Linux syscall backend.
+//!
+//! The syscall number goes into `x8`, up to six arguments go into
+//! `x0..x5`, and `svc #0` is used to enter the kernel. The result comes
+//! back in `x0`, so `x0` is an in/out register: it carries the first
+//! argument in and the return value out.
+//!
+//! aarch64 uses the "generic" modern Linux syscall table
+//! (`include/uapi/asm-generic/unistd.h`), which dropped several legacy
+//! calls in favour of their `*at` siblings - most notably there is no
+//! plain `open`, only `openat`.
+//!
+//! Reference:
+//! <https://github.com/torvalds/linux/blob/master/arch/arm64/kernel/syscall.c>
+//! <https://github.com/torvalds/linux/blob/master/include/uapi/asm-generic/unistd.h>
+
+use core::arch::asm;
+
+/// Small subset of the available Linux syscalls, numbered as in
+/// `include/uapi/asm-generic/unistd.h`.
+#[repr(u64)]
+pub(crate) enum LinuxSysCalls {
+    OpenAt = 56,
+    Close = 57,
+    Read = 63,
+    Write = 64,
+    WriteV = 66,
+    PreadV = 69,
+    PwriteV = 70,
+    PreadV2 = 286,
+    PwriteV2 = 287,
+    Munmap = 215,
+    Mmap = 222,
+    /// Only ever constructed from `rt::exit_group`, which is `no_std`-only.
+    #[cfg_attr(not(feature = "no_std"), allow(dead_code))]
+    ExitGroup = 94,
+    ClockGettime = 113,
+    Getpid = 172,
+}
+
+/// # Safety
+/// The caller has to make sure that `num` refers to a syscall that really
+/// doesn't take any arguments.
+pub(crate) unsafe fn syscall_0(num: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        lateout("x0") ret,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and `arg1` form a valid syscall
+/// invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_1(num: u64, arg1: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_2(num: u64, arg1: u64, arg2: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        in("x1") arg2,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_3(num: u64, arg1: u64, arg2: u64, arg3: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        in("x1") arg2,
+        in("x2") arg3,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_4(num: u64, arg1: u64, arg2: u64, arg3: u64, arg4: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        in("x1") arg2,
+        in("x2") arg3,
+        in("x3") arg4,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_5(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        in("x1") arg2,
+        in("x2") arg3,
+        in("x3") arg4,
+        in("x4") arg5,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_6(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+    arg6: u64,
+) -> i64 {
+    let ret: i64;
+    asm!(
+        "svc #0",
+        in("x8") num,
+        inlateout("x0") arg1 as i64 => ret,
+        in("x1") arg2,
+        in("x2") arg3,
+        in("x3") arg4,
+        in("x4") arg5,
+        in("x5") arg6,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
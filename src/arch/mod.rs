@@ -0,0 +1,34 @@
+//! Architecture-specific raw syscall backends.
+//!
+//! Each submodule implements the `syscall_0..syscall_6` primitives for one
+//! CPU architecture, using whatever instruction and register convention
+//! that architecture's Linux ABI mandates, plus that architecture's
+//! syscall number table (`LinuxSysCalls`): the numbers are *not* portable
+//! across architectures (and not every architecture implements every
+//! syscall under the same name - e.g. aarch64 and riscv64 only have
+//! `openat`, not `open`).
+//!
+//! Only the module matching the compilation target is compiled in;
+//! [`current`] re-exports it so the rest of the crate can stay
+//! architecture-agnostic.
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) mod aarch64;
+#[cfg(target_arch = "riscv64")]
+pub(crate) mod riscv64;
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod x86_64;
+
+#[cfg(not(any(
+    target_arch = "x86_64",
+    target_arch = "aarch64",
+    target_arch = "riscv64"
+)))]
+compile_error!("Unsupported architecture: only x86_64, aarch64 and riscv64 are implemented");
+
+#[cfg(target_arch = "aarch64")]
+pub(crate) use aarch64 as current;
+#[cfg(target_arch = "riscv64")]
+pub(crate) use riscv64 as current;
+#[cfg(target_arch = "x86_64")]
+pub(crate) use x86_64 as current;
@@ -0,0 +1,167 @@
+//! riscv64 Linux syscall backend.
+//!
+//! The syscall number goes into `a7`, up to six arguments go into
+//! `a0..a5`, and `ecall` is used to enter the kernel. The result comes
+//! back in `a0`, so `a0` is an in/out register just like `x0` on aarch64.
+//! No `-m`, `-a` or `-c` target features are required for this - `ecall`
+//! is part of the base RV64I instruction set.
+//!
+//! riscv64 also uses the "generic" modern Linux syscall table
+//! (`include/uapi/asm-generic/unistd.h`, the same one aarch64 uses), so
+//! just like on aarch64 there is no plain `open`, only `openat`.
+//!
+//! Reference:
+//! <https://github.com/torvalds/linux/blob/master/arch/riscv/kernel/entry.S>
+//! <https://github.com/torvalds/linux/blob/master/include/uapi/asm-generic/unistd.h>
+
+use core::arch::asm;
+
+/// Small subset of the available Linux syscalls, numbered as in
+/// `include/uapi/asm-generic/unistd.h`.
+#[repr(u64)]
+pub(crate) enum LinuxSysCalls {
+    OpenAt = 56,
+    Close = 57,
+    Read = 63,
+    Write = 64,
+    WriteV = 66,
+    PreadV = 69,
+    PwriteV = 70,
+    PreadV2 = 286,
+    PwriteV2 = 287,
+    Munmap = 215,
+    Mmap = 222,
+    /// Only ever constructed from `rt::exit_group`, which is `no_std`-only.
+    #[cfg_attr(not(feature = "no_std"), allow(dead_code))]
+    ExitGroup = 94,
+    ClockGettime = 113,
+    Getpid = 172,
+}
+
+/// # Safety
+/// The caller has to make sure that `num` refers to a syscall that really
+/// doesn't take any arguments.
+pub(crate) unsafe fn syscall_0(num: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        lateout("a0") ret,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and `arg1` form a valid syscall
+/// invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_1(num: u64, arg1: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_2(num: u64, arg1: u64, arg2: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        in("a1") arg2,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_3(num: u64, arg1: u64, arg2: u64, arg3: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        in("a1") arg2,
+        in("a2") arg3,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_4(num: u64, arg1: u64, arg2: u64, arg3: u64, arg4: u64) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        in("a1") arg2,
+        in("a2") arg3,
+        in("a3") arg4,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_5(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        in("a1") arg2,
+        in("a2") arg3,
+        in("a3") arg4,
+        in("a4") arg5,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_6(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+    arg6: u64,
+) -> i64 {
+    let ret: i64;
+    asm!(
+        "ecall",
+        in("a7") num,
+        inlateout("a0") arg1 as i64 => ret,
+        in("a1") arg2,
+        in("a2") arg3,
+        in("a3") arg4,
+        in("a4") arg5,
+        in("a5") arg6,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
@@ -0,0 +1,204 @@
+//! x86_64 Linux syscall backend.
+//!
+//! The syscall number goes into `rax`, up to six arguments go into
+//! `rdi, rsi, rdx, r10, r8, r9` (note the fourth argument is `r10`, *not*
+//! `rcx`, see [`syscall_4`]), and the `syscall` instruction is used to
+//! enter the kernel. The result (or negated error code) comes back in
+//! `rax`.
+//!
+//! Reference:
+//! <https://github.com/torvalds/linux/blob/master/arch/x86/entry/entry_64.S#L69>
+//! <https://github.com/torvalds/linux/blob/master/arch/x86/entry/syscalls/syscall_64.tbl>
+
+use core::arch::asm;
+
+/// Small subset of the available Linux syscalls, numbered as in
+/// `syscall_64.tbl`.
+#[repr(u64)]
+pub(crate) enum LinuxSysCalls {
+    Read = 0,
+    Write = 1,
+    Mmap = 9,
+    Munmap = 11,
+    Open = 2,
+    Close = 3,
+    Getpid = 39,
+    WriteV = 20,
+    PreadV = 295,
+    PwriteV = 296,
+    PreadV2 = 327,
+    PwriteV2 = 328,
+    /// Only ever constructed from `rt::exit_group`, which is `no_std`-only.
+    #[cfg_attr(not(feature = "no_std"), allow(dead_code))]
+    ExitGroup = 231,
+    ClockGettime = 228,
+}
+
+/// Wrapper around a Linux syscall with zero arguments. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` refers to a syscall that really
+/// doesn't take any arguments.
+pub(crate) unsafe fn syscall_0(num: u64) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with one argument. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` and `arg1` form a valid syscall
+/// invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_1(num: u64, arg1: u64) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with two arguments. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_2(num: u64, arg1: u64, arg2: u64) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        in("rsi") arg2,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with three arguments. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_3(num: u64, arg1: u64, arg2: u64, arg3: u64) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        in("rsi") arg2,
+        in("rdx") arg3,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with four arguments. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// Note that the fourth argument goes into `r10`, *not* `rcx`: the
+/// `syscall` instruction itself clobbers `rcx` (it stores the return
+/// address there), so the x86_64 Linux ABI substitutes `r10` for the
+/// fourth argument register compared to the regular SysV calling
+/// convention.
+///
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_4(num: u64, arg1: u64, arg2: u64, arg3: u64, arg4: u64) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        in("rsi") arg2,
+        in("rdx") arg3,
+        in("r10") arg4,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with five arguments. Returns the syscall
+/// result (or negated error code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_5(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        in("rsi") arg2,
+        in("rdx") arg3,
+        in("r10") arg4,
+        in("r8") arg5,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
+
+/// Wrapper around a Linux syscall with six arguments, the maximum the
+/// x86_64 Linux ABI supports. Returns the syscall result (or negated error
+/// code) that gets stored in `rax`.
+///
+/// # Safety
+/// The caller has to make sure that `num` and the arguments form a valid
+/// syscall invocation, e.g. that pointers passed as arguments are valid.
+pub(crate) unsafe fn syscall_6(
+    num: u64,
+    arg1: u64,
+    arg2: u64,
+    arg3: u64,
+    arg4: u64,
+    arg5: u64,
+    arg6: u64,
+) -> i64 {
+    let mut ret: i64;
+    asm!(
+        "syscall",
+        inlateout("rax") num as i64 => ret,
+        in("rdi") arg1,
+        in("rsi") arg2,
+        in("rdx") arg3,
+        in("r10") arg4,
+        in("r8") arg5,
+        in("r9") arg6,
+        lateout("rcx") _,
+        lateout("r11") _,
+        options(nostack, preserves_flags)
+    );
+    ret
+}
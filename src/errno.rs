@@ -0,0 +1,157 @@
+//! Typed error codes for raw Linux syscall results.
+//!
+//! The x86_64 Linux kernel ABI guarantees that a syscall either returns a
+//! non-negative success value, or a negated error code in the range
+//! `-4095..=-1` (see
+//! <https://github.com/torvalds/linux/blob/master/include/uapi/asm-generic/errno-base.h>
+//! and
+//! <https://github.com/torvalds/linux/blob/master/include/uapi/asm-generic/errno.h>).
+//! No real-world error number ever exceeds 4095, so this range can be
+//! distinguished from a large, legitimate success value (e.g. a byte count)
+//! without ambiguity.
+
+// `core::fmt` rather than `std::fmt` so this module compiles unchanged
+// whether or not the `no_std` feature is enabled.
+use core::fmt;
+
+/// Subset of the Linux `errno` values, using the asm-generic numbering that
+/// is shared across all architectures.
+#[repr(i32)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+pub enum Errno {
+    /// Operation not permitted.
+    EPERM = 1,
+    /// No such file or directory.
+    ENOENT = 2,
+    /// No such process.
+    ESRCH = 3,
+    /// Interrupted system call.
+    EINTR = 4,
+    /// I/O error.
+    EIO = 5,
+    /// Try again.
+    EAGAIN = 11,
+    /// Out of memory.
+    ENOMEM = 12,
+    /// Permission denied.
+    EACCES = 13,
+    /// Bad address.
+    EFAULT = 14,
+    /// Device or resource busy.
+    EBUSY = 16,
+    /// File exists.
+    EEXIST = 17,
+    /// Invalid cross-device link.
+    EXDEV = 18,
+    /// No such device.
+    ENODEV = 19,
+    /// Not a directory.
+    ENOTDIR = 20,
+    /// Is a directory.
+    EISDIR = 21,
+    /// Invalid argument.
+    EINVAL = 22,
+    /// File table overflow.
+    ENFILE = 23,
+    /// Too many open files.
+    EMFILE = 24,
+    /// File too large.
+    EFBIG = 27,
+    /// No space left on device.
+    ENOSPC = 28,
+    /// Illegal seek.
+    ESPIPE = 29,
+    /// Read-only file system.
+    EROFS = 30,
+    /// Broken pipe.
+    EPIPE = 32,
+    /// Bad file number, i.e. an invalid file descriptor.
+    EBADF = 9,
+    /// Some other, unmapped `errno` value.
+    ///
+    /// Kept so that [`Errno::from_syscall_ret`] stays total: this crate only
+    /// enumerates the errno values it actually cares about above, but the
+    /// kernel can return any value in `1..=4095`.
+    Unknown(i32),
+}
+
+impl Errno {
+    /// Turns a raw syscall return value (as found in `rax` after a
+    /// `syscall` instruction) into a `Result`.
+    ///
+    /// Values in `-4095..=-1` are interpreted as the negated `errno`, every
+    /// other value (including negative ones outside that band, which the
+    /// ABI never produces) is treated as a successful, non-negative result.
+    pub fn from_syscall_ret(ret: i64) -> Result<u64, Errno> {
+        if (-4095..=-1).contains(&ret) {
+            Err(Errno::from_raw(-ret as i32))
+        } else {
+            Ok(ret as u64)
+        }
+    }
+
+    /// Maps a positive `errno` number to its symbolic variant, falling back
+    /// to [`Errno::Unknown`] for values this crate doesn't name explicitly.
+    fn from_raw(errno: i32) -> Self {
+        match errno {
+            1 => Errno::EPERM,
+            2 => Errno::ENOENT,
+            3 => Errno::ESRCH,
+            4 => Errno::EINTR,
+            5 => Errno::EIO,
+            9 => Errno::EBADF,
+            11 => Errno::EAGAIN,
+            12 => Errno::ENOMEM,
+            13 => Errno::EACCES,
+            14 => Errno::EFAULT,
+            16 => Errno::EBUSY,
+            17 => Errno::EEXIST,
+            18 => Errno::EXDEV,
+            19 => Errno::ENODEV,
+            20 => Errno::ENOTDIR,
+            21 => Errno::EISDIR,
+            22 => Errno::EINVAL,
+            23 => Errno::ENFILE,
+            24 => Errno::EMFILE,
+            27 => Errno::EFBIG,
+            28 => Errno::ENOSPC,
+            29 => Errno::ESPIPE,
+            30 => Errno::EROFS,
+            32 => Errno::EPIPE,
+            other => Errno::Unknown(other),
+        }
+    }
+}
+
+impl fmt::Display for Errno {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Errno::EPERM => write!(f, "EPERM (operation not permitted)"),
+            Errno::ENOENT => write!(f, "ENOENT (no such file or directory)"),
+            Errno::ESRCH => write!(f, "ESRCH (no such process)"),
+            Errno::EINTR => write!(f, "EINTR (interrupted system call)"),
+            Errno::EIO => write!(f, "EIO (I/O error)"),
+            Errno::EBADF => write!(f, "EBADF (bad file descriptor)"),
+            Errno::EAGAIN => write!(f, "EAGAIN (try again)"),
+            Errno::ENOMEM => write!(f, "ENOMEM (out of memory)"),
+            Errno::EACCES => write!(f, "EACCES (permission denied)"),
+            Errno::EFAULT => write!(f, "EFAULT (bad address)"),
+            Errno::EBUSY => write!(f, "EBUSY (device or resource busy)"),
+            Errno::EEXIST => write!(f, "EEXIST (file exists)"),
+            Errno::EXDEV => write!(f, "EXDEV (invalid cross-device link)"),
+            Errno::ENODEV => write!(f, "ENODEV (no such device)"),
+            Errno::ENOTDIR => write!(f, "ENOTDIR (not a directory)"),
+            Errno::EISDIR => write!(f, "EISDIR (is a directory)"),
+            Errno::EINVAL => write!(f, "EINVAL (invalid argument)"),
+            Errno::ENFILE => write!(f, "ENFILE (file table overflow)"),
+            Errno::EMFILE => write!(f, "EMFILE (too many open files)"),
+            Errno::EFBIG => write!(f, "EFBIG (file too large)"),
+            Errno::ENOSPC => write!(f, "ENOSPC (no space left on device)"),
+            Errno::ESPIPE => write!(f, "ESPIPE (illegal seek)"),
+            Errno::EROFS => write!(f, "EROFS (read-only file system)"),
+            Errno::EPIPE => write!(f, "EPIPE (broken pipe)"),
+            Errno::Unknown(errno) => write!(f, "errno {}", errno),
+        }
+    }
+}
@@ -0,0 +1,80 @@
+//! The `no_std` counterpart of the `main()` demo in `main.rs`: the same
+//! "write hello world, then write/read a file" walkthrough, but without
+//! `std::ffi::CStr` or `format!` - everything here goes through stack
+//! buffers and the raw `sys_*` syscalls, so this module has to compile and
+//! run with nothing but `core` available.
+
+use crate::rt::write_decimal;
+use crate::{
+    sys_clock_gettime, sys_close, sys_getpid, sys_mmap, sys_munmap, sys_open, sys_read, sys_write,
+    LinuxFileFlags, CLOCK_REALTIME, MAP_ANONYMOUS, MAP_PRIVATE, PROT_READ, PROT_WRITE,
+};
+
+const STDOUT_FD: u64 = 1;
+
+/// Entry point called by [`crate::rt::entry`]. Mirrors `main()`, minus the
+/// vectored I/O parts (`writev`, `pwritev`/`preadv` and their `*2`
+/// variants), which depend on `std::ffi::CStr`, and minus the
+/// `gettimeofday` call.
+pub(crate) fn run() {
+    let string = b"hello world (no_std)\n";
+    sys_write(STDOUT_FD, string.as_ptr(), string.len() as u64).ok();
+
+    let fd = sys_open(
+        // null terminated - important here!
+        b"./foo.txt\0".as_ptr(),
+        LinuxFileFlags::O_CREAT as u32 | LinuxFileFlags::O_WRONLY as u32,
+        0o777,
+    );
+    let fd = match fd {
+        Ok(fd) => fd,
+        Err(_) => return,
+    };
+
+    let msg = b"hello, this was written to the file (no_std)\n";
+    sys_write(fd, msg.as_ptr(), msg.len() as u64).ok();
+
+    let fd = match sys_open(b"./foo.txt\0".as_ptr(), LinuxFileFlags::O_RDONLY as u32, 0) {
+        Ok(fd) => fd,
+        Err(_) => return,
+    };
+
+    let mut data = [0_u8; 1024];
+    if let Ok(n) = sys_read(fd, data.as_mut_ptr(), data.len() as u64) {
+        let prefix = b"read ";
+        sys_write(STDOUT_FD, prefix.as_ptr(), prefix.len() as u64).ok();
+        write_decimal(STDOUT_FD, n);
+        let suffix = b" bytes from foo.txt\n";
+        sys_write(STDOUT_FD, suffix.as_ptr(), suffix.len() as u64).ok();
+    }
+
+    sys_close(fd).ok();
+
+    if let Ok(ts) = sys_clock_gettime(CLOCK_REALTIME) {
+        let prefix = b"clock_gettime(CLOCK_REALTIME).tv_sec = ";
+        sys_write(STDOUT_FD, prefix.as_ptr(), prefix.len() as u64).ok();
+        write_decimal(STDOUT_FD, ts.tv_sec as u64);
+        let nl = b"\n";
+        sys_write(STDOUT_FD, nl.as_ptr(), nl.len() as u64).ok();
+    }
+
+    if let Ok(pid) = sys_getpid() {
+        let prefix = b"getpid() = ";
+        sys_write(STDOUT_FD, prefix.as_ptr(), prefix.len() as u64).ok();
+        write_decimal(STDOUT_FD, pid);
+        let nl = b"\n";
+        sys_write(STDOUT_FD, nl.as_ptr(), nl.len() as u64).ok();
+    }
+
+    const PAGE_SIZE: u64 = 4096;
+    let prot = PROT_READ | PROT_WRITE;
+    let flags = MAP_PRIVATE | MAP_ANONYMOUS;
+    if let Ok(addr) = sys_mmap(0, PAGE_SIZE, prot, flags, -1, 0) {
+        let prefix = b"mmap() = ";
+        sys_write(STDOUT_FD, prefix.as_ptr(), prefix.len() as u64).ok();
+        write_decimal(STDOUT_FD, addr);
+        let nl = b"\n";
+        sys_write(STDOUT_FD, nl.as_ptr(), nl.len() as u64).ok();
+        sys_munmap(addr, PAGE_SIZE).ok();
+    }
+}
@@ -0,0 +1,135 @@
+//! Freestanding runtime for the `no_std` build.
+//!
+//! With the `no_std` feature enabled this crate drops `std` (and, via
+//! `#![no_main]`, libc's `_start`/`main` startup dance) entirely and
+//! becomes a genuine freestanding Linux binary: the kernel jumps straight
+//! into [`_start`] below with nothing set up but a stack, we never unwind
+//! (there's no unwinder without `std`), and [`panic`] reports the panic
+//! through a raw `write` syscall before leaving via `exit_group` - there is
+//! no libc, no allocator, and no fallback.
+//!
+//! That last claim only holds if `core`/`compiler_builtins` themselves
+//! don't quietly pull libc back in: the prebuilt `core` shipped for
+//! `*-unknown-linux-gnu` targets assumes a libc providing `memcpy`/
+//! `memset`/`memcmp`/`rust_eh_personality`, so linking this crate with
+//! `--features no_std` against the prebuilt standard library still fails
+//! with `undefined symbol` errors for those four. Building a genuinely
+//! libc-free binary on these targets needs nightly's `-Z build-std`, i.e.
+//!
+//! ```text
+//! cargo +nightly build --features no_std \
+//!     -Z build-std=core,compiler_builtins \
+//!     -Z build-std-features=compiler-builtins-mem
+//! ```
+//!
+//! which recompiles `core`/`compiler_builtins` from source with the
+//! `compiler-builtins-mem` feature, the one that provides those four
+//! symbols itself instead of expecting libc to.
+
+use crate::arch::current::{syscall_1, LinuxSysCalls};
+use core::arch::naked_asm;
+
+/// x86_64 process entry point. The kernel starts every process here with
+/// nothing initialized but the stack (which holds `argc`/`argv`/`envp`/the
+/// aux vector, none of which this demo needs) - no prologue has run, so
+/// this has to be `#[unsafe(naked)]`: a non-naked function would emit a
+/// prologue that touches the stack/frame pointer before we've set either
+/// up.
+///
+/// The SysV ABI requires the stack to be 16-byte aligned at a `call`
+/// instruction; the kernel only guarantees it's aligned at process entry,
+/// so we re-align it defensively before calling into Rust code proper.
+#[cfg(target_arch = "x86_64")]
+#[unsafe(naked)]
+#[no_mangle]
+unsafe extern "C" fn _start() -> ! {
+    naked_asm!(
+        "xor ebp, ebp",
+        "and rsp, -16",
+        "call {entry}",
+        entry = sym entry
+    )
+}
+
+/// aarch64 process entry point, see [`_start`] above (x86_64) for the
+/// general rationale. AAPCS64 guarantees the stack is already 16-byte
+/// aligned at process entry, so unlike on x86_64 no re-alignment is
+/// needed here.
+#[cfg(target_arch = "aarch64")]
+#[unsafe(naked)]
+#[no_mangle]
+unsafe extern "C" fn _start() -> ! {
+    naked_asm!("bl {entry}", entry = sym entry)
+}
+
+/// riscv64 process entry point, see [`_start`] above (x86_64) for the
+/// general rationale.
+#[cfg(target_arch = "riscv64")]
+#[unsafe(naked)]
+#[no_mangle]
+unsafe extern "C" fn _start() -> ! {
+    naked_asm!("call {entry}", entry = sym entry)
+}
+
+/// The actual Rust entry point, called by the tiny `_start` shim above
+/// once the stack is in a state ordinary (non-naked) Rust code can run on.
+extern "C" fn entry() -> ! {
+    crate::no_std_demo::run();
+    exit_group(0)
+}
+
+/// Linux `exit_group()` system call: terminates every thread in the
+/// calling process's thread group with exit status `code`. Never returns.
+fn exit_group(code: i32) -> ! {
+    unsafe {
+        syscall_1(LinuxSysCalls::ExitGroup as u64, code as u64);
+    }
+    // exit_group never actually returns; spin defensively in case the
+    // syscall is somehow intercepted (e.g. under a seccomp filter or a
+    // ptrace-based sandbox) and control comes back to us anyway.
+    loop {
+        core::hint::spin_loop()
+    }
+}
+
+/// Widest decimal representation of a `u64` (`u64::MAX` is 20 digits).
+const MAX_U64_DECIMAL_DIGITS: usize = 20;
+
+/// Formats `n` as decimal ASCII into `buf` and returns the written
+/// sub-slice (right-aligned within `buf`). Exists because `no_std` has no
+/// `format!`/`ToString` without pulling in `alloc`, and this crate wants to
+/// stay allocator-free.
+///
+/// `buf` is always [`MAX_U64_DECIMAL_DIGITS`] long, which fits every `u64`.
+fn u64_to_decimal(mut n: u64, buf: &mut [u8; MAX_U64_DECIMAL_DIGITS]) -> &[u8] {
+    let mut i = buf.len();
+    loop {
+        i -= 1;
+        buf[i] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    &buf[i..]
+}
+
+/// Writes `n` as decimal ASCII to `fd` via a raw `write` syscall.
+pub(crate) fn write_decimal(fd: u64, n: u64) {
+    let mut buf = [0_u8; MAX_U64_DECIMAL_DIGITS];
+    let digits = u64_to_decimal(n, &mut buf);
+    crate::sys_write(fd, digits.as_ptr(), digits.len() as u64).ok();
+}
+
+/// File descriptor of the standard error stream, used by [`panic`].
+const STDERR_FD: u64 = 2;
+
+/// Writes a fixed `panic: aborting` message to stderr and terminates the
+/// process with a non-zero status. Neither the panic message nor its
+/// location is reported.
+#[panic_handler]
+fn panic(_info: &core::panic::PanicInfo) -> ! {
+    let msg = b"panic: aborting\n";
+    crate::sys_write(STDERR_FD, msg.as_ptr(), msg.len() as u64).ok();
+    exit_group(1)
+}
@@ -0,0 +1,47 @@
+//! Reads this process's auxiliary vector to find `AT_SYSINFO_EHDR`, the
+//! load address of the vDSO.
+//!
+//! The auxiliary vector is normally handed to a process on the initial
+//! stack, right after `argc`/`argv`/`envp` (see the comment on `_start` in
+//! [`crate::rt`]) - but this crate's `std` build goes through the regular
+//! Rust/glibc-free startup, which never hands that pointer back to us.
+//! `/proc/self/auxv` is the kernel re-exposing the exact same
+//! `Elf64_auxv_t` array as a byte stream instead, so it can be read with a
+//! plain `read()` syscall rather than by walking raw stack memory - one
+//! more place this crate can stay libc-free while still reaching data
+//! that's normally only handed to a process at start-up.
+//!
+//! <https://man7.org/linux/man-pages/man3/getauxval.3.html>
+
+use crate::LinuxFileFlags::O_RDONLY;
+use crate::{sys_open, sys_read};
+
+/// Auxiliary vector entry carrying the vDSO's load address. See
+/// `include/uapi/linux/auxvec.h`.
+const AT_SYSINFO_EHDR: u64 = 33;
+/// Terminates the auxiliary vector.
+const AT_NULL: u64 = 0;
+
+/// Looks up `AT_SYSINFO_EHDR`, returning the vDSO's load address, or
+/// `None` if the kernel didn't map one (e.g. under some emulators, or
+/// architectures without a vDSO) or `/proc` isn't mounted.
+pub(crate) fn vdso_load_address() -> Option<u64> {
+    let fd = sys_open(b"/proc/self/auxv\0".as_ptr(), O_RDONLY as u32, 0).ok()?;
+
+    // `/proc/self/auxv` is a few dozen 16-byte `(type, value)` pairs at
+    // most - 4 KiB comfortably fits it on every architecture this crate
+    // supports.
+    let mut buf = [0_u8; 4096];
+    let n = sys_read(fd, buf.as_mut_ptr(), buf.len() as u64).ok()? as usize;
+
+    buf[..n]
+        .chunks_exact(16)
+        .map(|entry| {
+            let ty = u64::from_ne_bytes(entry[0..8].try_into().unwrap());
+            let value = u64::from_ne_bytes(entry[8..16].try_into().unwrap());
+            (ty, value)
+        })
+        .take_while(|&(ty, _)| ty != AT_NULL)
+        .find(|&(ty, _)| ty == AT_SYSINFO_EHDR)
+        .map(|(_, value)| value)
+}
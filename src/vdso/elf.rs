@@ -0,0 +1,175 @@
+//! Minimal, read-only ELF64 parsing: just enough to walk the vDSO image's
+//! program headers, dynamic section and symbol hash table to resolve a
+//! symbol by name - nowhere near a general-purpose ELF loader.
+//!
+//! The vDSO is already mapped and fixed up by the kernel before the
+//! process ever runs, so unlike a regular `ld.so` there is no relocation
+//! or section loading to do here, only walking structures that are
+//! already valid at `base`.
+//!
+//! Modeled after the kernel's own minimal vDSO parser, which is a good
+//! from-scratch reference for exactly this subset of ELF:
+//! <https://github.com/torvalds/linux/blob/master/tools/testing/selftests/vDSO/parse_vdso.c>
+//! Field layouts come from:
+//! <https://github.com/torvalds/linux/blob/master/include/uapi/linux/elf.h>
+
+use core::mem::size_of;
+
+/// `e_ident[0..4]`: every ELF file starts with this magic.
+const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+
+#[repr(C)]
+struct Elf64Ehdr {
+    e_ident: [u8; 16],
+    e_type: u16,
+    e_machine: u16,
+    e_version: u32,
+    e_entry: u64,
+    e_phoff: u64,
+    e_shoff: u64,
+    e_flags: u32,
+    e_ehsize: u16,
+    e_phentsize: u16,
+    e_phnum: u16,
+    e_shentsize: u16,
+    e_shnum: u16,
+    e_shstrndx: u16,
+}
+
+#[repr(C)]
+struct Elf64Phdr {
+    p_type: u32,
+    p_flags: u32,
+    p_offset: u64,
+    p_vaddr: u64,
+    p_paddr: u64,
+    p_filesz: u64,
+    p_memsz: u64,
+    p_align: u64,
+}
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+
+/// `Elf64_Dyn`. `d_val`/`d_ptr` are a union in the C header, but both
+/// members are a plain `u64` with no padding difference, so one field
+/// covers both uses.
+#[repr(C)]
+struct Elf64Dyn {
+    d_tag: i64,
+    d_val: u64,
+}
+
+const DT_NULL: i64 = 0;
+const DT_HASH: i64 = 4;
+const DT_STRTAB: i64 = 5;
+const DT_SYMTAB: i64 = 6;
+
+#[repr(C)]
+struct Elf64Sym {
+    st_name: u32,
+    st_info: u8,
+    st_other: u8,
+    st_shndx: u16,
+    st_value: u64,
+    st_size: u64,
+}
+
+/// Resolves `name` to a function pointer inside the vDSO mapped at
+/// `base`, or `None` if the image is malformed or doesn't export it.
+///
+/// # Safety
+/// `base` must be the load address of a valid vDSO image mapped by the
+/// kernel (i.e. what `AT_SYSINFO_EHDR` points to). This walks the ELF
+/// structures found there directly, with no bounds checking against the
+/// mapping's actual size - the kernel-provided vDSO is trusted the same
+/// way the kernel-provided auxiliary vector is.
+pub(crate) unsafe fn resolve_symbol(base: u64, name: &str) -> Option<u64> {
+    let ehdr = &*(base as *const Elf64Ehdr);
+    if ehdr.e_ident[0..4] != ELF_MAGIC {
+        return None;
+    }
+
+    let phdrs = core::slice::from_raw_parts(
+        (base + ehdr.e_phoff) as *const Elf64Phdr,
+        ehdr.e_phnum as usize,
+    );
+
+    // The vDSO is mapped in place rather than at its preferred address;
+    // the load bias is the delta between where it actually ended up and
+    // the vaddr its first `PT_LOAD` segment claims, and has to be added to
+    // every vaddr below (including the `PT_DYNAMIC` one) to get a real
+    // pointer.
+    let load_bias = base.wrapping_sub(phdrs.iter().find(|p| p.p_type == PT_LOAD)?.p_vaddr);
+    let dynamic = load_bias.wrapping_add(phdrs.iter().find(|p| p.p_type == PT_DYNAMIC)?.p_vaddr);
+
+    let mut strtab = 0_u64;
+    let mut symtab = 0_u64;
+    let mut hash = 0_u64;
+
+    let mut entry = dynamic as *const Elf64Dyn;
+    loop {
+        let d = &*entry;
+        match d.d_tag {
+            DT_NULL => break,
+            DT_STRTAB => strtab = load_bias.wrapping_add(d.d_val),
+            DT_SYMTAB => symtab = load_bias.wrapping_add(d.d_val),
+            DT_HASH => hash = load_bias.wrapping_add(d.d_val),
+            _ => {}
+        }
+        entry = entry.add(1);
+    }
+
+    if strtab == 0 || symtab == 0 || hash == 0 {
+        return None;
+    }
+
+    // SysV `.hash` section layout: `nbucket`, `nchain`, then the bucket
+    // and chain arrays (ELF gABI, chapter 5, "Hash Table").
+    let nbucket = *(hash as *const u32) as u64;
+    let buckets = (hash + 8) as *const u32;
+    let chains = (hash + 8 + nbucket * 4) as *const u32;
+
+    let mut index = *buckets.add((elf_hash(name) as u64 % nbucket) as usize);
+    while index != 0 {
+        let sym = &*((symtab + index as u64 * size_of::<Elf64Sym>() as u64) as *const Elf64Sym);
+        let sym_name = (strtab + sym.st_name as u64) as *const u8;
+        if cstr_eq(sym_name, name.as_bytes()) {
+            return Some(load_bias.wrapping_add(sym.st_value));
+        }
+        index = *chains.add(index as usize);
+    }
+    None
+}
+
+/// Compares a NUL-terminated C string at `ptr` against `name` byte-by-byte,
+/// without going through `core::ffi::CStr` - `CStr::from_ptr` calls out to
+/// an external `strlen`, which would reintroduce a libc dependency into an
+/// otherwise libc-free path.
+///
+/// # Safety
+/// `ptr` must point at a valid, NUL-terminated byte string.
+unsafe fn cstr_eq(ptr: *const u8, name: &[u8]) -> bool {
+    for (i, &want) in name.iter().enumerate() {
+        if *ptr.add(i) != want {
+            return false;
+        }
+    }
+    *ptr.add(name.len()) == 0
+}
+
+/// The classic SysV ELF hash function used to index a `.hash` section's
+/// bucket array. See the ELF gABI, chapter 5, "Hash Table", for the
+/// reference algorithm this is a direct transcription of.
+fn elf_hash(name: &str) -> u32 {
+    let mut h: u32 = 0;
+    for &byte in name.as_bytes() {
+        h = (h << 4).wrapping_add(byte as u32);
+        let g = h & 0xf000_0000;
+        if g != 0 {
+            h ^= g >> 24;
+        }
+        h &= !g;
+    }
+    h
+}
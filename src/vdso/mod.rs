@@ -0,0 +1,99 @@
+//! vDSO-accelerated time syscalls.
+//!
+//! The kernel maps a small ELF image - the vDSO ("virtual dynamic shared
+//! object") - into every process, exporting a handful of syscalls as
+//! ordinary functions that read `CLOCK_MONOTONIC`/`CLOCK_REALTIME`
+//! straight out of a shared kernel/userspace memory page, with no mode
+//! switch at all. [`auxv`] finds where the kernel mapped it,
+//! [`elf::resolve_symbol`] resolves a function inside it by name, and the
+//! two `sys_*` functions below in `main.rs` call the result as a plain
+//! function pointer, falling back to the real syscall when the vDSO
+//! doesn't export what they're after.
+//!
+//! Resolution only has to happen once per process, since the vDSO's
+//! mapping never moves or changes after start-up; [`resolve`] caches the
+//! result (or the fact that resolution failed) in a static so repeated
+//! calls are just an atomic load.
+//!
+//! <https://man7.org/linux/man-pages/man7/vdso.7.html>
+
+mod auxv;
+mod elf;
+
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// `struct timespec` as the kernel/vDSO ABI defines it.
+#[repr(C)]
+pub(crate) struct Timespec {
+    pub(crate) tv_sec: i64,
+    pub(crate) tv_nsec: i64,
+}
+
+/// `struct timeval` as the kernel/vDSO ABI defines it.
+#[repr(C)]
+pub(crate) struct Timeval {
+    pub(crate) tv_sec: i64,
+    pub(crate) tv_usec: i64,
+}
+
+/// Not yet looked up.
+const UNRESOLVED: u64 = 0;
+/// Looked up once already, and the vDSO didn't export it.
+const ABSENT: u64 = 1;
+
+static VDSO_CLOCK_GETTIME: AtomicU64 = AtomicU64::new(UNRESOLVED);
+static VDSO_GETTIMEOFDAY: AtomicU64 = AtomicU64::new(UNRESOLVED);
+
+/// Looks up `symbol`, going through `cache` so the vDSO is only ever
+/// parsed once per process regardless of how often the caller asks.
+///
+/// `UNRESOLVED`/`ABSENT` double as sentinels here because a real function
+/// pointer is never `0` or `1`.
+fn resolve(cache: &AtomicU64, symbol: &str) -> Option<u64> {
+    match cache.load(Ordering::Acquire) {
+        UNRESOLVED => {
+            // Two threads racing here just do the same read-only lookup
+            // twice; whichever store wins, both observed the same result,
+            // so there's no need for anything fancier than `Relaxed`.
+            let found = lookup(symbol).unwrap_or(ABSENT);
+            cache.store(found, Ordering::Relaxed);
+            resolve(cache, symbol)
+        }
+        ABSENT => None,
+        addr => Some(addr),
+    }
+}
+
+fn lookup(symbol: &str) -> Option<u64> {
+    let base = auxv::vdso_load_address()?;
+    unsafe { elf::resolve_symbol(base, symbol) }
+}
+
+/// Resolves `__vdso_clock_gettime`, or `None` if the vDSO doesn't export
+/// it (falling back to the raw `clock_gettime` syscall is then up to the
+/// caller).
+///
+/// # Safety
+/// The returned address, if any, must only be called with the same
+/// signature `__vdso_clock_gettime` actually has: `(clockid: i32, tp: *mut
+/// Timespec) -> i32`, exactly like the real `clock_gettime(2)`.
+pub(crate) fn clock_gettime() -> Option<unsafe extern "C" fn(i32, *mut Timespec) -> i32> {
+    resolve(&VDSO_CLOCK_GETTIME, "__vdso_clock_gettime").map(|addr| unsafe {
+        core::mem::transmute::<u64, unsafe extern "C" fn(i32, *mut Timespec) -> i32>(addr)
+    })
+}
+
+/// Resolves `__vdso_gettimeofday`, or `None` if the vDSO doesn't export
+/// it.
+///
+/// # Safety
+/// The returned address, if any, must only be called with the same
+/// signature `__vdso_gettimeofday` actually has: `(tv: *mut Timeval, tz:
+/// *mut u8) -> i32`, exactly like the real `gettimeofday(2)` (`tz` is
+/// always passed as `null` here, matching every modern caller - the
+/// kernel has ignored a non-null timezone for decades).
+pub(crate) fn gettimeofday() -> Option<unsafe extern "C" fn(*mut Timeval, *mut u8) -> i32> {
+    resolve(&VDSO_GETTIMEOFDAY, "__vdso_gettimeofday").map(|addr| unsafe {
+        core::mem::transmute::<u64, unsafe extern "C" fn(*mut Timeval, *mut u8) -> i32>(addr)
+    })
+}